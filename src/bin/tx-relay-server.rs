@@ -1,7 +1,8 @@
 use anyhow::Result;
-use bitcoin_nostr_relay::{BitcoinNostrRelay, Network, RelayConfig};
+use bitcoin_nostr_relay::BitcoinNostrRelay;
 use std::env;
-use tracing::{info, warn};
+use tracing::info;
+use tx_relay::{config, shutdown_signal};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -16,29 +17,9 @@ async fn main() -> Result<()> {
     }
     
     // Determine relay configuration based on environment and arguments
-    let relay_id = args.get(1)
-        .and_then(|arg| arg.parse::<u16>().ok())
-        .unwrap_or(1);
-        
-    let bitcoin_chain = env::var("BITCOIN_CHAIN").unwrap_or_else(|_| "regtest".to_string());
+    let config = config::from_args(&args)?;
     
-    // Create configuration based on chain type using new API
-    let config = match bitcoin_chain.as_str() {
-        "testnet4" => {
-            info!("Using testnet4 configuration");
-            RelayConfig::for_network(Network::Testnet4, relay_id)
-        },
-        "regtest" | _ => {
-            if bitcoin_chain != "regtest" {
-                warn!("Unknown chain '{}', defaulting to regtest", bitcoin_chain);
-            }
-            info!("Using regtest configuration");
-            RelayConfig::for_network(Network::Regtest, relay_id)
-        }
-    };
-    
-    info!("🚀 Starting TX Relay Server {}", relay_id);
-    info!("🔗 Chain: {}", bitcoin_chain);
+    info!("🚀 Starting TX Relay Server {}", config.relay_id);
     info!("📊 Bitcoin RPC: {}", config.bitcoin_rpc_url);
     info!("📡 Strfry URL: {}", config.strfry_url);
     info!("🔌 WebSocket: {}", config.websocket_listen_addr);
@@ -50,8 +31,12 @@ async fn main() -> Result<()> {
     
     info!("🎯 Relay server started - monitoring mempool and relaying transactions");
     
-    // Start the relay (this will run indefinitely)
-    relay.start().await?;
+    // Exit on CTRL-C/SIGTERM. In-flight sends aren't flushed and the strfry WebSocket
+    // isn't closed; that needs a start_with_shutdown in bitcoin-nostr-relay
+    tokio::select! {
+        result = relay.start() => result?,
+        _ = shutdown_signal() => info!("🛑 Shutting down relay server {}", relay.config().relay_id),
+    }
     
     Ok(())
 }
@@ -60,18 +45,24 @@ fn print_usage() {
     println!("TX Relay Server - Bitcoin transaction relay over Nostr");
     println!();
     println!("USAGE:");
-    println!("    tx-relay-server [RELAY_ID]");
+    println!("    tx-relay-server [RELAY_ID] [--config <PATH>]");
     println!();
     println!("ARGUMENTS:");
     println!("    <RELAY_ID>    Relay identifier (1 or 2) [default: 1]");
     println!();
+    println!("OPTIONS:");
+    println!("    --config <PATH>    Load configuration from a TOML file; can't be combined");
+    println!("                       with RELAY_ID (see config/tx-relay.example.toml)");
+    println!();
     println!("ENVIRONMENT VARIABLES:");
     println!("    BITCOIN_CHAIN    Bitcoin network (regtest, testnet4) [default: regtest]");
+    println!("                     Overrides the network in a --config file when set");
     println!();
     println!("EXAMPLES:");
     println!("    tx-relay-server              # Start relay 1 on regtest");
     println!("    tx-relay-server 2            # Start relay 2 on regtest");
     println!("    BITCOIN_CHAIN=testnet4 tx-relay-server 1    # Start relay 1 on testnet4");
+    println!("    tx-relay-server --config relay.toml         # Start relay from a config file");
     println!();
     println!("The relay will:");
     println!("  - Monitor Bitcoin node mempool for new transactions");  
@@ -0,0 +1,108 @@
+use bitcoin_nostr_relay::Network;
+use std::env;
+use std::fmt;
+
+/// Error returned when a chain name can't be mapped to a supported [`Network`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNetworkError {
+    /// A real Bitcoin network that bitcoin-nostr-relay has no preset for yet
+    Unsupported(String),
+    /// A name that isn't a known Bitcoin network
+    Unknown(String),
+}
+
+impl fmt::Display for ParseNetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(chain) => write!(f, "Unsupported chain '{}' (supported: regtest, testnet4)", chain),
+            Self::Unknown(chain) => write!(f, "Unknown chain '{}' (supported: regtest, testnet4)", chain),
+        }
+    }
+}
+
+impl std::error::Error for ParseNetworkError {}
+
+/// Parse a chain name such as the `BITCOIN_CHAIN` value into a [`Network`]
+///
+/// Matching is case-insensitive. `test`/`testnet` refer to testnet3 in Bitcoin Core,
+/// so they are reported as unsupported rather than silently mapped to testnet4.
+pub fn parse_network(chain: &str) -> Result<Network, ParseNetworkError> {
+    match chain.trim().to_ascii_lowercase().as_str() {
+        "regtest" => Ok(Network::Regtest),
+        "testnet4" => Ok(Network::Testnet4),
+        "main" | "mainnet" | "bitcoin" | "signet" | "test" | "testnet" | "testnet3" => {
+            Err(ParseNetworkError::Unsupported(chain.to_string()))
+        }
+        _ => Err(ParseNetworkError::Unknown(chain.to_string())),
+    }
+}
+
+/// Read the network from `BITCOIN_CHAIN`, defaulting to regtest only when it is unset
+///
+/// An unsupported or unknown value is an error rather than a silent regtest relay.
+pub fn network_from_env() -> Result<Network, ParseNetworkError> {
+    network_or_regtest(env::var("BITCOIN_CHAIN").ok().as_deref())
+}
+
+/// [`network_from_env`] with the `BITCOIN_CHAIN` value passed in explicitly
+fn network_or_regtest(bitcoin_chain: Option<&str>) -> Result<Network, ParseNetworkError> {
+    bitcoin_chain.map_or(Ok(Network::Regtest), parse_network)
+}
+
+/// Chain name as used by `BITCOIN_CHAIN` and Bitcoin Core
+pub fn chain_name(network: Network) -> &'static str {
+    match network {
+        Network::Regtest => "regtest",
+        Network::Testnet4 => "testnet4",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_network_supported() {
+        assert_eq!(parse_network("regtest"), Ok(Network::Regtest));
+        assert_eq!(parse_network("testnet4"), Ok(Network::Testnet4));
+        assert_eq!(parse_network(" RegTest\n"), Ok(Network::Regtest));
+        assert_eq!(parse_network("  TESTNET4 "), Ok(Network::Testnet4));
+    }
+
+    #[test]
+    fn test_parse_network_unsupported() {
+        for chain in ["main", "mainnet", "bitcoin", "signet", "test", "testnet", "testnet3", "MainNet"] {
+            assert_eq!(
+                parse_network(chain),
+                Err(ParseNetworkError::Unsupported(chain.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_network_unknown() {
+        assert_eq!(parse_network("foo"), Err(ParseNetworkError::Unknown("foo".to_string())));
+        assert_eq!(parse_network(""), Err(ParseNetworkError::Unknown(String::new())));
+    }
+
+    #[test]
+    fn test_network_or_regtest() {
+        assert_eq!(network_or_regtest(None), Ok(Network::Regtest));
+        assert_eq!(network_or_regtest(Some("testnet4")), Ok(Network::Testnet4));
+        assert_eq!(
+            network_or_regtest(Some("mainnet")),
+            Err(ParseNetworkError::Unsupported("mainnet".to_string()))
+        );
+        assert_eq!(
+            network_or_regtest(Some("foo")),
+            Err(ParseNetworkError::Unknown("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_chain_name_round_trip() {
+        for network in [Network::Regtest, Network::Testnet4] {
+            assert_eq!(parse_network(chain_name(network)), Ok(network));
+        }
+    }
+}
@@ -0,0 +1,460 @@
+use crate::chain;
+use anyhow::{bail, Context, Result};
+use bitcoin_nostr_relay::{ConfigError, Network, RelayConfig, ValidationConfig};
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Relay configuration as read from a TOML file; every field is optional
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    network: Option<String>,
+    relay_id: Option<u16>,
+    bitcoin_rpc_url: Option<String>,
+    bitcoin_rpc_auth: Option<RpcAuthFile>,
+    strfry_url: Option<String>,
+    websocket_listen_addr: Option<SocketAddr>,
+    mempool_poll_interval_secs: Option<u64>,
+    max_client_connections: Option<usize>,
+    websocket_buffer_size: Option<usize>,
+    validation: Option<ValidationFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RpcAuthFile {
+    username: String,
+    password: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ValidationFile {
+    enable_validation: Option<bool>,
+    enable_precheck: Option<bool>,
+    validation_timeout_ms: Option<u64>,
+    cache_ttl_seconds: Option<u64>,
+    cache_size: Option<usize>,
+}
+
+/// Load a relay configuration from a TOML file
+///
+/// Missing fields fall back to the `RelayConfig::for_network` defaults. `BITCOIN_CHAIN`
+/// overrides the file's `network` when set (with a warning if they differ), and the
+/// network defaults to regtest when neither is given; `relay_id` defaults to 1. See `config/tx-relay.example.toml` for
+/// the available fields.
+pub fn load(path: &Path) -> Result<RelayConfig> {
+    load_with_chain(path, env::var("BITCOIN_CHAIN").ok().as_deref())
+}
+
+/// [`load`] with the `BITCOIN_CHAIN` value passed in explicitly
+fn load_with_chain(path: &Path, bitcoin_chain: Option<&str>) -> Result<RelayConfig> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    let file: ConfigFile = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+
+    let file_network = file.network.as_deref().map(chain::parse_network).transpose()?;
+    let env_network = bitcoin_chain
+        .map(chain::parse_network)
+        .transpose()
+        .context("Invalid BITCOIN_CHAIN")?;
+    if let (Some(env_network), Some(file_network)) = (env_network, file_network) {
+        if env_network != file_network {
+            warn!(
+                "BITCOIN_CHAIN={} overrides network = \"{}\" in {}",
+                chain::chain_name(env_network),
+                chain::chain_name(file_network),
+                path.display()
+            );
+        }
+    }
+    let network = env_network.or(file_network).unwrap_or(Network::Regtest);
+    let relay_id = file.relay_id.unwrap_or(1);
+    info!("Using {} configuration from {}", chain::chain_name(network), path.display());
+
+    let mut builder = RelayConfigBuilder::for_network(network, relay_id);
+
+    if let Some(url) = file.bitcoin_rpc_url {
+        builder = builder.bitcoin_rpc_url(url);
+    }
+    if let Some(auth) = file.bitcoin_rpc_auth {
+        builder = builder.bitcoin_rpc_auth(auth.username, auth.password);
+    }
+    if let Some(url) = file.strfry_url {
+        builder = builder.strfry_url(url);
+    }
+    if let Some(addr) = file.websocket_listen_addr {
+        builder = builder.websocket_listen_addr(addr);
+    }
+    if let Some(secs) = file.mempool_poll_interval_secs {
+        builder = builder.mempool_poll_interval(Duration::from_secs(secs));
+    }
+    if let Some(max) = file.max_client_connections {
+        builder = builder.max_client_connections(max);
+    }
+    if let Some(size) = file.websocket_buffer_size {
+        builder = builder.websocket_buffer_size(size);
+    }
+    if let Some(validation) = file.validation {
+        let mut validation_config = ValidationConfig::default();
+        if let Some(enable) = validation.enable_validation {
+            validation_config.enable_validation = enable;
+        }
+        if let Some(enable) = validation.enable_precheck {
+            validation_config.enable_precheck = enable;
+        }
+        if let Some(timeout_ms) = validation.validation_timeout_ms {
+            validation_config.validation_timeout_ms = timeout_ms;
+        }
+        if let Some(ttl) = validation.cache_ttl_seconds {
+            validation_config.cache_ttl_seconds = ttl;
+        }
+        if let Some(size) = validation.cache_size {
+            validation_config.cache_size = size;
+        }
+        builder = builder.validation_config(validation_config);
+    }
+
+    // build() rejects a zero websocket_buffer_size or mempool_poll_interval_secs
+    builder
+        .build()
+        .with_context(|| format!("Invalid config file {}", path.display()))
+}
+
+/// Select the relay configuration from the command line arguments
+///
+/// `--config <PATH>` loads the file with [`load`]. Otherwise the first argument is the
+/// relay id (default 1) and the network comes from `BITCOIN_CHAIN`. Passing a relay id
+/// together with `--config` is an error, since the file already decides it.
+pub fn from_args(args: &[String]) -> Result<RelayConfig> {
+    let relay_id = args.get(1).and_then(|arg| arg.parse::<u16>().ok());
+
+    let config_path = args
+        .iter()
+        .position(|arg| arg == "--config")
+        .map(|i| args.get(i + 1).map(PathBuf::from).context("--config requires a path"))
+        .transpose()?;
+
+    match config_path {
+        Some(path) => {
+            if let Some(relay_id) = relay_id {
+                bail!(
+                    "RELAY_ID {} can't be combined with --config; set relay_id in {} instead",
+                    relay_id,
+                    path.display()
+                );
+            }
+            load(&path)
+        }
+        None => {
+            let network = chain::network_from_env().context("Invalid BITCOIN_CHAIN")?;
+            info!("Using {} configuration", chain::chain_name(network));
+            Ok(RelayConfigBuilder::for_network(network, relay_id.unwrap_or(1)).build()?)
+        }
+    }
+}
+
+/// Builder for [`RelayConfig`] that starts from the network presets
+///
+/// `RelayConfig::for_network` only covers the fixed local port layout, so this lets
+/// callers override individual fields (RPC ports in CI, strfry endpoints in staging,
+/// validation timeouts) without rebuilding the whole struct by hand.
+#[derive(Debug, Clone)]
+pub struct RelayConfigBuilder {
+    network: Network,
+    preset_id: u16,
+    config: RelayConfig,
+}
+
+impl RelayConfigBuilder {
+    /// Start from the `RelayConfig::for_network` defaults
+    ///
+    /// An unsupported `(network, relay_id)` pair is reported by [`build`](Self::build)
+    /// instead of panicking like `for_network`.
+    pub fn for_network(network: Network, relay_id: u16) -> Self {
+        let config = if has_preset(network, relay_id) {
+            RelayConfig::for_network(network, relay_id)
+        } else {
+            RelayConfig::default()
+        };
+
+        Self {
+            network,
+            preset_id: relay_id,
+            config,
+        }
+    }
+
+    /// Set the Bitcoin RPC URL
+    pub fn bitcoin_rpc_url(mut self, url: impl Into<String>) -> Self {
+        self.config.bitcoin_rpc_url = url.into();
+        self
+    }
+
+    /// Set the Bitcoin RPC credentials
+    pub fn bitcoin_rpc_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.config = self.config.with_auth(username.into(), password.into());
+        self
+    }
+
+    /// Set the strfry Nostr relay URL
+    pub fn strfry_url(mut self, url: impl Into<String>) -> Self {
+        self.config.strfry_url = url.into();
+        self
+    }
+
+    /// Set the relay identifier
+    pub fn relay_id(mut self, relay_id: impl Into<String>) -> Self {
+        self.config.relay_id = relay_id.into();
+        self
+    }
+
+    /// Set the WebSocket server listen address
+    pub fn websocket_listen_addr(mut self, addr: SocketAddr) -> Self {
+        self.config.websocket_listen_addr = addr;
+        self
+    }
+
+    /// Set the mempool polling interval
+    pub fn mempool_poll_interval(mut self, interval: Duration) -> Self {
+        self.config = self.config.with_mempool_poll_interval(interval);
+        self
+    }
+
+    /// Set the transaction validation configuration
+    pub fn validation_config(mut self, config: ValidationConfig) -> Self {
+        self.config = self.config.with_validation(config);
+        self
+    }
+
+    /// Set the maximum number of concurrent client connections
+    pub fn max_client_connections(mut self, max: usize) -> Self {
+        self.config.max_client_connections = max;
+        self
+    }
+
+    /// Set the WebSocket buffer size for client connections
+    pub fn websocket_buffer_size(mut self, size: usize) -> Self {
+        self.config.websocket_buffer_size = size;
+        self
+    }
+
+    /// Finish the configuration
+    ///
+    /// Rejects a `(network, relay_id)` pair without a preset, applies the URL and relay
+    /// id checks from `RelayConfig::new`, and rejects a zero
+    /// WebSocket buffer size (the relay's broadcast channel panics on it) and a zero
+    /// mempool poll interval (which would busy-loop on the Bitcoin RPC).
+    pub fn build(self) -> Result<RelayConfig, ConfigError> {
+        if !has_preset(self.network, self.preset_id) {
+            return Err(ConfigError::UnsupportedConfiguration {
+                network: self.network,
+                relay_id: self.preset_id,
+            });
+        }
+
+        RelayConfig::new(
+            &self.config.bitcoin_rpc_url,
+            &self.config.strfry_url,
+            &self.config.relay_id,
+            self.config.websocket_listen_addr,
+        )?;
+
+        if self.config.websocket_buffer_size == 0 {
+            return Err(ConfigError::InvalidParameter {
+                param: "websocket_buffer_size must be greater than 0".to_string(),
+            });
+        }
+
+        if self.config.mempool_poll_interval.is_zero() {
+            return Err(ConfigError::InvalidParameter {
+                param: "mempool_poll_interval must be greater than 0".to_string(),
+            });
+        }
+
+        Ok(self.config)
+    }
+}
+
+/// Whether `RelayConfig::for_network` has a preset for the pair instead of panicking
+fn has_preset(network: Network, relay_id: u16) -> bool {
+    matches!(
+        (network, relay_id),
+        (Network::Regtest, 1 | 2) | (Network::Testnet4, 1 | 2)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Deref;
+
+    /// Config file in the temp directory, removed again when dropped
+    struct TempConfig(PathBuf);
+
+    impl Deref for TempConfig {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    /// Write `contents` to a per-test file in the temp directory
+    fn write_config(name: &str, contents: &str) -> TempConfig {
+        let path = env::temp_dir().join(format!("tx-relay-{}-{}.toml", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        TempConfig(path)
+    }
+
+    #[test]
+    fn test_load_defaults() {
+        let path = write_config("defaults", "");
+        let config = load_with_chain(&path, None).unwrap();
+        let preset = RelayConfig::for_network(Network::Regtest, 1);
+
+        assert_eq!(config.bitcoin_rpc_url, preset.bitcoin_rpc_url);
+        assert_eq!(config.strfry_url, preset.strfry_url);
+        assert_eq!(config.relay_id, "1");
+        assert_eq!(config.websocket_listen_addr, preset.websocket_listen_addr);
+        assert_eq!(config.mempool_poll_interval, preset.mempool_poll_interval);
+        assert_eq!(config.max_client_connections, preset.max_client_connections);
+        assert_eq!(config.websocket_buffer_size, preset.websocket_buffer_size);
+        assert!(config.validation_config.enable_validation);
+    }
+
+    #[test]
+    fn test_load_overrides() {
+        let path = write_config(
+            "overrides",
+            r#"
+            network = "testnet4"
+            relay_id = 2
+            bitcoin_rpc_url = "http://127.0.0.1:28332"
+            strfry_url = "ws://127.0.0.1:8777"
+            websocket_listen_addr = "0.0.0.0:8779"
+            mempool_poll_interval_secs = 5
+            max_client_connections = 10
+            websocket_buffer_size = 50
+
+            [bitcoin_rpc_auth]
+            username = "alice"
+            password = "secret"
+
+            [validation]
+            enable_validation = false
+            cache_size = 10
+            "#,
+        );
+        let config = load_with_chain(&path, None).unwrap();
+
+        assert_eq!(config.bitcoin_rpc_url, "http://127.0.0.1:28332");
+        assert_eq!(config.strfry_url, "ws://127.0.0.1:8777");
+        assert_eq!(config.relay_id, "2");
+        assert_eq!(config.websocket_listen_addr, "0.0.0.0:8779".parse::<SocketAddr>().unwrap());
+        assert_eq!(config.mempool_poll_interval, Duration::from_secs(5));
+        assert_eq!(config.max_client_connections, 10);
+        assert_eq!(config.websocket_buffer_size, 50);
+        assert_eq!(config.bitcoin_rpc_auth.username, "alice");
+        assert_eq!(config.bitcoin_rpc_auth.password, "secret");
+        assert!(!config.validation_config.enable_validation);
+        assert!(config.validation_config.enable_precheck);
+        assert_eq!(config.validation_config.cache_size, 10);
+    }
+
+    #[test]
+    fn test_load_network_precedence() {
+        let testnet4 = RelayConfig::for_network(Network::Testnet4, 1).bitcoin_rpc_url;
+        let regtest = RelayConfig::for_network(Network::Regtest, 1).bitcoin_rpc_url;
+
+        let path = write_config("precedence", r#"network = "testnet4""#);
+        assert_eq!(load_with_chain(&path, None).unwrap().bitcoin_rpc_url, testnet4);
+        assert_eq!(load_with_chain(&path, Some("regtest")).unwrap().bitcoin_rpc_url, regtest);
+
+        assert!(load_with_chain(&path, Some("mainnet")).is_err());
+        assert!(load_with_chain(&path, Some("bogus")).is_err());
+
+        let path = write_config("precedence-env-only", "");
+        assert_eq!(load_with_chain(&path, Some("testnet4")).unwrap().bitcoin_rpc_url, testnet4);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_keys() {
+        let path = write_config("unknown-key", "relay_idd = 1");
+        assert!(load_with_chain(&path, None).is_err());
+
+        let path = write_config("unknown-nested-key", "[validation]\ncache = 10");
+        assert!(load_with_chain(&path, None).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_bad_values() {
+        for (name, contents) in [
+            ("relay-id-zero", "relay_id = 0"),
+            ("relay-id-three", "relay_id = 3"),
+            ("unsupported-network", r#"network = "mainnet""#),
+            ("unknown-network", r#"network = "bogus""#),
+            ("zero-buffer", "websocket_buffer_size = 0"),
+            ("zero-poll-interval", "mempool_poll_interval_secs = 0"),
+        ] {
+            let path = write_config(name, contents);
+            assert!(load_with_chain(&path, None).is_err(), "{} should be rejected", name);
+        }
+    }
+
+    #[test]
+    fn test_builder_rejects_zero_values() {
+        let result = RelayConfigBuilder::for_network(Network::Regtest, 1)
+            .websocket_buffer_size(0)
+            .build();
+        assert!(matches!(result, Err(ConfigError::InvalidParameter { .. })));
+
+        let result = RelayConfigBuilder::for_network(Network::Regtest, 1)
+            .mempool_poll_interval(Duration::ZERO)
+            .build();
+        assert!(matches!(result, Err(ConfigError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn test_builder_rejects_unsupported_relay_id() {
+        for relay_id in [0, 3] {
+            let result = RelayConfigBuilder::for_network(Network::Testnet4, relay_id).build();
+            assert!(matches!(
+                result,
+                Err(ConfigError::UnsupportedConfiguration { network: Network::Testnet4, relay_id: id }) if id == relay_id
+            ));
+        }
+    }
+
+    #[test]
+    fn test_from_args_rejects_unsupported_relay_id() {
+        let args = vec!["tx-relay-server".to_string(), "3".to_string()];
+        let err = from_args(&args).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ConfigError>(),
+            Some(ConfigError::UnsupportedConfiguration { relay_id: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_args_rejects_relay_id_with_config() {
+        let args: Vec<String> = ["tx-relay-server", "2", "--config", "relay.toml"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let err = from_args(&args).unwrap_err();
+        assert!(err.to_string().contains("can't be combined with --config"));
+    }
+}
@@ -1,2 +1,11 @@
-// This library has been replaced by the bitcoin-nostr-relay crate
-pub use bitcoin_nostr_relay::*;
\ No newline at end of file
+// Relay functionality lives in the bitcoin-nostr-relay crate; this crate only adds
+// the glue shared by the binaries
+pub use bitcoin_nostr_relay::*;
+
+pub mod chain;
+pub mod config;
+pub mod shutdown;
+
+pub use chain::{parse_network, ParseNetworkError};
+pub use config::RelayConfigBuilder;
+pub use shutdown::shutdown_signal;
@@ -1,7 +1,8 @@
 use anyhow::Result;
-use bitcoin_nostr_relay::{BitcoinNostrRelay, Network, RelayConfig};
+use bitcoin_nostr_relay::BitcoinNostrRelay;
 use tracing::info;
 use std::env;
+use tx_relay::{config, shutdown_signal};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -10,34 +11,20 @@ async fn main() -> Result<()> {
     info!("Starting TxRelay - Bitcoin to Nostr transaction relay using library");
     
     // Determine relay configuration based on environment
-    let relay_id = env::args().nth(1)
-        .and_then(|arg| arg.parse::<u16>().ok())
-        .unwrap_or(1);
-        
-    let bitcoin_chain = env::var("BITCOIN_CHAIN").unwrap_or_else(|_| "regtest".to_string());
+    let args: Vec<String> = env::args().collect();
+    let config = config::from_args(&args)?;
     
-    // Create configuration based on chain type using new API
-    let config = match bitcoin_chain.as_str() {
-        "testnet4" => {
-            info!("Using testnet4 configuration");
-            RelayConfig::for_network(Network::Testnet4, relay_id)
-        },
-        "regtest" | _ => {
-            if bitcoin_chain != "regtest" {
-                info!("Unknown chain '{}', defaulting to regtest", bitcoin_chain);
-            }
-            info!("Using regtest configuration");
-            RelayConfig::for_network(Network::Regtest, relay_id)
-        }
-    };
-    
-    info!("Starting relay {} for {} chain", relay_id, bitcoin_chain);
+    info!("Starting relay {}", config.relay_id);
     info!("Bitcoin RPC: {}", config.bitcoin_rpc_url);
     info!("Strfry URL: {}", config.strfry_url);
     
     // Create and start the relay
     let mut relay = BitcoinNostrRelay::new(config)?;
-    relay.start().await?;
+    // Exit on CTRL-C/SIGTERM; flushing and closing the relay needs upstream support
+    tokio::select! {
+        result = relay.start() => result?,
+        _ = shutdown_signal() => info!("Shutting down relay"),
+    }
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+use tokio::signal;
+use tracing::info;
+
+/// Resolve when the process receives CTRL-C or, on Unix, SIGTERM
+///
+/// The binaries race this against `BitcoinNostrRelay::start` and exit when it wins;
+/// the relay itself isn't told to stop.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c().await.expect("Failed to install CTRL-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received CTRL-C"),
+        _ = terminate => info!("Received SIGTERM"),
+    }
+}